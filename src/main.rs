@@ -1,14 +1,22 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+#[cfg(windows)]
 use std::{
     ffi::OsStr,
     os::windows::ffi::OsStrExt,
+    os::windows::fs::MetadataExt,
     os::windows::io::AsRawHandle,
-    path::PathBuf,
     ptr,
 };
 
 use owo_colors::OwoColorize;
+use serde::{Deserialize, Serialize};
 use structopt::StructOpt;
 
+#[cfg(windows)]
 #[link(name = "kernel32")]
 extern "system" {
     fn CreateDirectoryW(
@@ -21,11 +29,157 @@ extern "system" {
         lpTargetFileName: *const u16,
         dwFlags: u32,
     ) -> u8;
+
+    fn CreateFileW(
+        lpFileName: *const u16,
+        dwDesiredAccess: u32,
+        dwShareMode: u32,
+        lpSecurityAttributes: *const std::ffi::c_void,
+        dwCreationDisposition: u32,
+        dwFlagsAndAttributes: u32,
+        hTemplateFile: *mut std::ffi::c_void,
+    ) -> *mut std::ffi::c_void;
+
+    fn DeviceIoControl(
+        hDevice: *mut std::ffi::c_void,
+        dwIoControlCode: u32,
+        lpInBuffer: *const std::ffi::c_void,
+        nInBufferSize: u32,
+        lpOutBuffer: *mut std::ffi::c_void,
+        nOutBufferSize: u32,
+        lpBytesReturned: *mut u32,
+        lpOverlapped: *const std::ffi::c_void,
+    ) -> i32;
+
+    fn CloseHandle(hObject: *mut std::ffi::c_void) -> i32;
+
+    fn GetCurrentProcess() -> *mut std::ffi::c_void;
+
+    fn GetLastError() -> u32;
+
+    fn GetFileInformationByHandle(
+        hFile: *mut std::ffi::c_void,
+        lpFileInformation: *mut ByHandleFileInformation,
+    ) -> i32;
+}
+
+#[cfg(windows)]
+#[repr(C)]
+#[allow(dead_code)]
+struct FileTime {
+    low_date_time: u32,
+    high_date_time: u32,
+}
+
+#[cfg(windows)]
+#[repr(C)]
+#[allow(dead_code)]
+struct ByHandleFileInformation {
+    file_attributes: u32,
+    creation_time: FileTime,
+    last_access_time: FileTime,
+    last_write_time: FileTime,
+    volume_serial_number: u32,
+    file_size_high: u32,
+    file_size_low: u32,
+    number_of_links: u32,
+    file_index_high: u32,
+    file_index_low: u32,
 }
 
+#[cfg(windows)]
+#[link(name = "advapi32")]
+extern "system" {
+    fn OpenProcessToken(
+        ProcessHandle: *mut std::ffi::c_void,
+        DesiredAccess: u32,
+        TokenHandle: *mut *mut std::ffi::c_void,
+    ) -> i32;
+
+    fn LookupPrivilegeValueW(
+        lpSystemName: *const u16,
+        lpName: *const u16,
+        lpLuid: *mut Luid,
+    ) -> i32;
+
+    fn AdjustTokenPrivileges(
+        TokenHandle: *mut std::ffi::c_void,
+        DisableAllPrivileges: i32,
+        NewState: *mut TokenPrivileges,
+        BufferLengthInBytes: u32,
+        PreviousState: *mut std::ffi::c_void,
+        ReturnLengthInBytes: *mut u32,
+    ) -> i32;
+}
+
+#[cfg(windows)]
+#[repr(C)]
+struct Luid {
+    low_part: u32,
+    high_part: i32,
+}
+
+#[cfg(windows)]
+#[repr(C)]
+struct LuidAndAttributes {
+    luid: Luid,
+    attributes: u32,
+}
+
+#[cfg(windows)]
+#[repr(C)]
+struct TokenPrivileges {
+    privilege_count: u32,
+    privileges: [LuidAndAttributes; 1],
+}
+
+#[cfg(windows)]
 const SYMBOLIC_LINK_FLAG_DIRECTORY: u32 = 1;
+#[cfg(windows)]
 const SYMBOLIC_LINK_FLAG_ALLOW_UNPRIVILEGED_CREATE: u32 = 2;
 
+#[cfg(windows)]
+const GENERIC_READ: u32 = 0x8000_0000;
+#[cfg(windows)]
+const OPEN_EXISTING: u32 = 3;
+#[cfg(windows)]
+const FILE_FLAG_BACKUP_SEMANTICS: u32 = 0x0200_0000;
+#[cfg(windows)]
+const FILE_FLAG_OPEN_REPARSE_POINT: u32 = 0x0020_0000;
+#[cfg(windows)]
+const INVALID_HANDLE_VALUE: isize = -1;
+
+#[cfg(windows)]
+const GENERIC_WRITE: u32 = 0x4000_0000;
+
+#[cfg(windows)]
+const TOKEN_ADJUST_PRIVILEGES: u32 = 0x0020;
+#[cfg(windows)]
+const TOKEN_QUERY: u32 = 0x0008;
+#[cfg(windows)]
+const SE_PRIVILEGE_ENABLED: u32 = 0x0000_0002;
+#[cfg(windows)]
+const ERROR_NOT_ALL_ASSIGNED: u32 = 1300;
+
+#[cfg(windows)]
+const FSCTL_GET_REPARSE_POINT: u32 = 0x0009_00A8;
+#[cfg(windows)]
+const FSCTL_DELETE_REPARSE_POINT: u32 = 0x0009_00AC;
+#[cfg(windows)]
+const MAXIMUM_REPARSE_DATA_BUFFER_SIZE: usize = 16 * 1024;
+#[cfg(windows)]
+const REPARSE_GUID_DATA_BUFFER_HEADER_SIZE: usize = 24; // ReparseTag + ReparseDataLength + Reserved + GUID
+
+#[cfg(windows)]
+const IO_REPARSE_TAG_MOUNT_POINT: u32 = 0xA000_0003;
+#[cfg(windows)]
+const IO_REPARSE_TAG_SYMLINK: u32 = 0xA000_000C;
+
+#[cfg(windows)]
+const FILE_ATTRIBUTE_DIRECTORY: u32 = 0x10;
+#[cfg(windows)]
+const FILE_ATTRIBUTE_REPARSE_POINT: u32 = 0x400;
+
 #[derive(StructOpt, Debug)]
 #[structopt(name = "CompilerLinker", about = "Portable symbolic, junction, and hard/soft link creator.")]
 struct CliOpts {
@@ -33,7 +187,7 @@ struct CliOpts {
     src: PathBuf,
 
     #[structopt(short = "o", long = "target", help = "Destination path the link points to")]
-    dst: PathBuf,
+    dst: Option<PathBuf>,
 
     #[structopt(short = "s", long = "soft", help = "Create a soft link (directory symlink)")]
     soft: bool,
@@ -46,9 +200,41 @@ struct CliOpts {
 
     #[structopt(short = "j", long = "junction", help = "Create a junction point")]
     junction: bool,
+
+    #[structopt(
+        short = "r",
+        long = "read",
+        alias = "inspect",
+        help = "Inspect an existing junction or symlink and report its type and target"
+    )]
+    read: bool,
+
+    #[structopt(
+        short = "x",
+        long = "remove",
+        help = "Remove a junction, symlink, or hard link without touching its target"
+    )]
+    remove: bool,
+
+    #[structopt(
+        long = "export",
+        help = "Export every link under <tree> to a JSON manifest",
+        number_of_values = 2,
+        value_names = &["tree", "manifest"]
+    )]
+    export: Option<Vec<PathBuf>>,
+
+    #[structopt(
+        long = "import",
+        help = "Recreate every link recorded in a JSON manifest under <root>",
+        number_of_values = 2,
+        value_names = &["manifest", "root"]
+    )]
+    import: Option<Vec<PathBuf>>,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 enum LinkType {
     Soft,
     Hard,
@@ -75,6 +261,46 @@ struct LinkError {
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let argv = CliOpts::from_args();
 
+    if argv.read {
+        match inspect_reparse_point(&argv.src) {
+            Ok(info) => print_inspect_result(&argv.src, &info),
+            Err(e) => {
+                eprintln!("{} {}", "error:".bright_red(), e.message.bright_red());
+                std::process::exit(e.exit_code);
+            }
+        }
+        return Ok(());
+    }
+
+    if argv.remove {
+        if let Err(e) = remove_link(&argv.src) {
+            eprintln!("{} {}", "error:".bright_red(), e.message.bright_red());
+            std::process::exit(e.exit_code);
+        }
+        println!("{} {:?}", "Removed".bright_green(), argv.src.bright_green());
+        return Ok(());
+    }
+
+    if let Some(values) = &argv.export {
+        let (tree, manifest) = (&values[0], &values[1]);
+        if let Err(e) = export_manifest(tree, manifest) {
+            eprintln!("{} {}", "error:".bright_red(), e.message.bright_red());
+            std::process::exit(e.exit_code);
+        }
+        println!("{} {:?}", "Exported manifest to".bright_green(), manifest.bright_green());
+        return Ok(());
+    }
+
+    if let Some(values) = &argv.import {
+        let (manifest, root) = (&values[0], &values[1]);
+        if let Err(e) = import_manifest(manifest, root) {
+            eprintln!("{} {}", "error:".bright_red(), e.message.bright_red());
+            std::process::exit(e.exit_code);
+        }
+        println!("{} {:?}", "Imported manifest into".bright_green(), root.bright_green());
+        return Ok(());
+    }
+
     let link_type = match parse_link_type(&argv) {
         Ok(lt) => lt,
         Err(e) => {
@@ -83,12 +309,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     };
 
-    if let Err(e) = create_link(link_type, &argv.src, &argv.dst) {
+    let dst = match argv.dst {
+        Some(ref dst) => dst.clone(),
+        None => {
+            eprintln!(
+                "{} {}",
+                "error:".bright_red(),
+                "Missing destination path. Use --target/-o.".bright_red()
+            );
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = create_link(link_type, &argv.src, &dst) {
         eprintln!("{} {}", "error:".bright_red(), e.message.bright_red());
         std::process::exit(e.exit_code);
     }
 
-    print_success(link_type, &argv.src, &argv.dst);
+    print_success(link_type, &argv.src, &dst);
     Ok(())
 }
 
@@ -127,31 +365,152 @@ fn create_link(link_type: LinkType, src: &PathBuf, dst: &PathBuf) -> Result<(),
         use std::os::windows::fs as winfs;
 
         match link_type {
-            LinkType::Junction => create_junction(dst, src),
-            LinkType::Symbolic => winfs::symlink_file(dst, src).map_err(|e| LinkError {
-                message: format!("Failed to create symbolic link: {}", e),
-                exit_code: 3,
-            }),
+            LinkType::Junction => {
+                // Best-effort: SeBackup/SeRestore are only needed to bypass ACL checks on
+                // paths the caller couldn't otherwise reach, so a normal write-access
+                // location must still work for a token that can't hold them.
+                let _ = enable_link_privileges(&["SeBackupPrivilege", "SeRestorePrivilege"]);
+                create_junction(dst, src)
+            }
+            LinkType::Symbolic => {
+                // Best-effort: an unprivileged, Developer-Mode-enabled account creates
+                // symlinks via SYMBOLIC_LINK_FLAG_ALLOW_UNPRIVILEGED_CREATE (which
+                // `winfs::symlink_file` already passes) without ever holding this privilege.
+                let _ = enable_link_privileges(&["SeCreateSymbolicLinkPrivilege"]);
+                winfs::symlink_file(dst, src).map_err(|e| LinkError {
+                    message: format!(
+                        "Failed to create symbolic link: {}. Re-run from an elevated shell or enable Developer Mode.",
+                        e
+                    ),
+                    exit_code: 3,
+                })
+            }
             LinkType::Hard => std::fs::hard_link(dst, src).map_err(|e| LinkError {
                 message: format!("Failed to create hard link: {}", e),
                 exit_code: 4,
             }),
-            LinkType::Soft => winfs::symlink_dir(dst, src).map_err(|e| LinkError {
-                message: format!("Failed to create soft link: {}", e),
-                exit_code: 5,
-            }),
+            LinkType::Soft => {
+                let _ = enable_link_privileges(&["SeCreateSymbolicLinkPrivilege"]);
+                winfs::symlink_dir(dst, src).map_err(|e| LinkError {
+                    message: format!(
+                        "Failed to create soft link: {}. Re-run from an elevated shell or enable Developer Mode.",
+                        e
+                    ),
+                    exit_code: 5,
+                })
+            }
         }
     }
 
     #[cfg(not(windows))]
     {
-        Err(LinkError {
-            message: "This utility only works on Windows.".to_string(),
-            exit_code: 7,
-        })
+        // Unix has no distinction between file and directory symlinks, so Symbolic and
+        // Soft both map to the same syscall; src/dst keep the same original/link ordering
+        // used on Windows so scripts stay portable across platforms.
+        match link_type {
+            LinkType::Symbolic | LinkType::Soft => {
+                std::os::unix::fs::symlink(dst, src).map_err(|e| LinkError {
+                    message: format!("Failed to create symbolic link: {}", e),
+                    exit_code: 3,
+                })
+            }
+            LinkType::Hard => std::fs::hard_link(dst, src).map_err(|e| LinkError {
+                message: format!("Failed to create hard link: {}", e),
+                exit_code: 4,
+            }),
+            LinkType::Junction => Err(LinkError {
+                message: "Junctions are a Windows-only reparse point; there is no Unix equivalent.".to_string(),
+                exit_code: 7,
+            }),
+        }
+    }
+}
+
+/// Best-effort: tries to enable the given privileges on the current process token, which
+/// widens access to junction/symlink creation in protected locations. Most callers don't
+/// hold these privileges (non-admin tokens, UAC-split admin tokens, unprivileged-symlink
+/// Developer Mode) and don't need to — the real `CreateDirectoryW`/`DeviceIoControl`/
+/// `symlink_*` call still succeeds in ordinary writable locations either way, so failures
+/// here are deliberately swallowed by callers rather than treated as fatal.
+#[cfg(windows)]
+fn enable_link_privileges(names: &[&str]) -> Result<(), LinkError> {
+    unsafe {
+        let mut token: *mut std::ffi::c_void = ptr::null_mut();
+        if OpenProcessToken(
+            GetCurrentProcess(),
+            TOKEN_ADJUST_PRIVILEGES | TOKEN_QUERY,
+            &mut token,
+        ) == 0
+        {
+            return Err(LinkError {
+                message: "Failed to open process token for privilege elevation".to_string(),
+                exit_code: 8,
+            });
+        }
+
+        // Try every privilege even after one fails, and report the first failure.
+        let mut result = Ok(());
+        for &name in names {
+            if let Err(e) = enable_privilege(token, name) {
+                if result.is_ok() {
+                    result = Err(e);
+                }
+            }
+        }
+
+        CloseHandle(token);
+        result
+    }
+}
+
+#[cfg(windows)]
+fn enable_privilege(token: *mut std::ffi::c_void, name: &str) -> Result<(), LinkError> {
+    unsafe {
+        let mut luid = Luid {
+            low_part: 0,
+            high_part: 0,
+        };
+
+        let name_wide = utf16_encode(std::path::Path::new(name));
+        if LookupPrivilegeValueW(ptr::null(), name_wide.as_ptr(), &mut luid) == 0 {
+            return Err(LinkError {
+                message: format!("{} is not a recognized privilege on this system", name),
+                exit_code: 8,
+            });
+        }
+
+        let mut privileges = TokenPrivileges {
+            privilege_count: 1,
+            privileges: [LuidAndAttributes {
+                luid,
+                attributes: SE_PRIVILEGE_ENABLED,
+            }],
+        };
+
+        let adjusted = AdjustTokenPrivileges(
+            token,
+            0,
+            &mut privileges,
+            std::mem::size_of::<TokenPrivileges>() as u32,
+            ptr::null_mut(),
+            ptr::null_mut(),
+        );
+
+        if adjusted == 0 || GetLastError() == ERROR_NOT_ALL_ASSIGNED {
+            return Err(LinkError {
+                message: format!(
+                    "Could not enable {}. Re-run from an elevated shell.",
+                    name
+                ),
+                exit_code: 8,
+            });
+        }
+
+        Ok(())
     }
 }
 
+#[cfg(windows)]
 fn utf16_encode(s: &std::path::Path) -> Vec<u16> {
     let mut encoded: Vec<u16> = OsStr::new(s.as_os_str())
         .encode_wide()
@@ -160,10 +519,25 @@ fn utf16_encode(s: &std::path::Path) -> Vec<u16> {
     encoded
 }
 
+#[cfg(windows)]
+fn canonicalize_target(target: &Path) -> Result<PathBuf, LinkError> {
+    let canonical = std::fs::canonicalize(target).map_err(|e| LinkError {
+        message: format!("Failed to resolve absolute path for {:?}: {}", target, e),
+        exit_code: 2,
+    })?;
+
+    // `canonicalize` returns a `\\?\`-prefixed path on Windows; junctions store a plain
+    // absolute path in PrintName, so strip the verbatim prefix back off.
+    let canonical_str = canonical.to_string_lossy();
+    let stripped = canonical_str.strip_prefix(r"\\?\").unwrap_or(&canonical_str);
+    Ok(PathBuf::from(stripped))
+}
+
+#[cfg(windows)]
 fn create_junction(target: &PathBuf, link: &PathBuf) -> Result<(), LinkError> {
     unsafe {
         let link_wide = utf16_encode(link);
-        let target_wide = utf16_encode(target);
+        let target = canonicalize_target(target)?;
 
         // Create the directory for the junction point
         if CreateDirectoryW(link_wide.as_ptr(), ptr::null()) == 0 {
@@ -175,7 +549,7 @@ fn create_junction(target: &PathBuf, link: &PathBuf) -> Result<(), LinkError> {
 
         // Create the junction using reparse points
         // A junction is created by writing a reparse point to the directory
-        let reparse_data = create_reparse_data(&target_wide)?;
+        let reparse_data = create_reparse_data(&target)?;
 
         let handle = std::fs::OpenOptions::new()
             .write(true)
@@ -185,20 +559,6 @@ fn create_junction(target: &PathBuf, link: &PathBuf) -> Result<(), LinkError> {
                 exit_code: 2,
             })?;
 
-        #[allow(non_snake_case)]
-        extern "system" {
-            fn DeviceIoControl(
-                hDevice: *mut std::ffi::c_void,
-                dwIoControlCode: u32,
-                lpInBuffer: *const std::ffi::c_void,
-                nInBufferSize: u32,
-                lpOutBuffer: *mut std::ffi::c_void,
-                nOutBufferSize: u32,
-                lpBytesReturned: *mut u32,
-                lpOverlapped: *const std::ffi::c_void,
-            ) -> i32;
-        }
-
         const FSCTL_SET_REPARSE_POINT: u32 = 0x900A4;
         let mut bytes_returned = 0u32;
 
@@ -214,7 +574,8 @@ fn create_junction(target: &PathBuf, link: &PathBuf) -> Result<(), LinkError> {
         ) == 0
         {
             return Err(LinkError {
-                message: "Failed to set reparse point for junction".to_string(),
+                message: "Failed to set reparse point for junction. Re-run from an elevated shell."
+                    .to_string(),
                 exit_code: 2,
             });
         }
@@ -223,45 +584,486 @@ fn create_junction(target: &PathBuf, link: &PathBuf) -> Result<(), LinkError> {
     }
 }
 
-fn create_reparse_data(target: &[u16]) -> Result<Vec<u8>, LinkError> {
-    const REPARSE_JUNCTION_DATA_BUFFER_HEADER_SIZE: usize = 8;
-    const IO_REPARSE_TAG_MOUNT_POINT: u32 = 0xA0000003;
+#[cfg(windows)]
+fn create_reparse_data(target: &Path) -> Result<Vec<u8>, LinkError> {
+    const REPARSE_DATA_BUFFER_HEADER_SIZE: usize = 8; // ReparseTag + ReparseDataLength + Reserved
+    const MOUNT_POINT_BUFFER_HEADER_SIZE: usize = 8; // the four u16 name offset/length fields
 
-    // Calculate sizes
-    let target_len = (target.len() - 1) * 2; // -1 for null terminator
-    let reparse_data_len = REPARSE_JUNCTION_DATA_BUFFER_HEADER_SIZE
-        + target_len * 2
-        + 4; // 4 bytes for null terminator space
+    // Junctions require an absolute target: SubstituteName is the NT device path
+    // (`\??\` + target) the filesystem actually follows, PrintName is the plain
+    // absolute path shown by Explorer and `dir`.
+    let substitute_name = format!(r"\??\{}", target.display());
+    let print_name = target.display().to_string();
 
-    let mut buffer = vec![0u8; reparse_data_len + 8];
+    let mut substitute_wide: Vec<u16> = OsStr::new(&substitute_name).encode_wide().collect();
+    substitute_wide.push(0); // NUL
+    let mut print_wide: Vec<u16> = OsStr::new(&print_name).encode_wide().collect();
+    print_wide.push(0); // NUL
 
-    // Write reparse tag
-    let tag = IO_REPARSE_TAG_MOUNT_POINT.to_le_bytes();
-    buffer[0..4].copy_from_slice(&tag);
+    let substitute_name_length = ((substitute_wide.len() - 1) * 2) as u16;
+    let print_name_length = ((print_wide.len() - 1) * 2) as u16;
+    let substitute_name_offset = 0u16;
+    let print_name_offset = (substitute_wide.len() * 2) as u16;
 
-    // Write reparse data length
-    let data_len = (reparse_data_len as u16).to_le_bytes();
-    buffer[4..6].copy_from_slice(&data_len);
+    let path_buffer_len = substitute_wide.len() * 2 + print_wide.len() * 2;
+    let reparse_data_len = (MOUNT_POINT_BUFFER_HEADER_SIZE + path_buffer_len) as u16;
 
-    // Write reserved field
-    buffer[6..8].copy_from_slice(&[0u8; 2]);
+    let mut buffer = vec![0u8; REPARSE_DATA_BUFFER_HEADER_SIZE + reparse_data_len as usize];
 
-    // Write path buffer offset and length
-    let path_offset = 0u16.to_le_bytes();
-    buffer[8..10].copy_from_slice(&path_offset);
+    buffer[0..4].copy_from_slice(&IO_REPARSE_TAG_MOUNT_POINT.to_le_bytes());
+    buffer[4..6].copy_from_slice(&reparse_data_len.to_le_bytes());
+    // buffer[6..8] is the Reserved field, left zeroed
 
-    let path_len = (target_len as u16).to_le_bytes();
-    buffer[10..12].copy_from_slice(&path_len);
+    buffer[8..10].copy_from_slice(&substitute_name_offset.to_le_bytes());
+    buffer[10..12].copy_from_slice(&substitute_name_length.to_le_bytes());
+    buffer[12..14].copy_from_slice(&print_name_offset.to_le_bytes());
+    buffer[14..16].copy_from_slice(&print_name_length.to_le_bytes());
 
-    // Write the target path
-    for (i, &wchar) in target.iter().take(target.len() - 1).enumerate() {
+    let path_buffer_start = REPARSE_DATA_BUFFER_HEADER_SIZE + MOUNT_POINT_BUFFER_HEADER_SIZE;
+    for (i, &wchar) in substitute_wide.iter().enumerate() {
         let bytes = wchar.to_le_bytes();
-        buffer[12 + i * 2..12 + i * 2 + 2].copy_from_slice(&bytes);
+        buffer[path_buffer_start + i * 2..path_buffer_start + i * 2 + 2].copy_from_slice(&bytes);
+    }
+
+    let print_buffer_start = path_buffer_start + substitute_wide.len() * 2;
+    for (i, &wchar) in print_wide.iter().enumerate() {
+        let bytes = wchar.to_le_bytes();
+        buffer[print_buffer_start + i * 2..print_buffer_start + i * 2 + 2].copy_from_slice(&bytes);
     }
 
     Ok(buffer)
 }
 
+struct ReparseInfo {
+    tag_name: &'static str,
+    target: String,
+}
+
+#[cfg(windows)]
+fn open_reparse_point(path: &PathBuf, access: u32) -> Result<*mut std::ffi::c_void, LinkError> {
+    unsafe {
+        let path_wide = utf16_encode(path);
+
+        let handle = CreateFileW(
+            path_wide.as_ptr(),
+            access,
+            0,
+            ptr::null(),
+            OPEN_EXISTING,
+            FILE_FLAG_OPEN_REPARSE_POINT | FILE_FLAG_BACKUP_SEMANTICS,
+            ptr::null_mut(),
+        );
+
+        if handle as isize == INVALID_HANDLE_VALUE {
+            return Err(LinkError {
+                message: format!("Failed to open {:?}", path),
+                exit_code: 6,
+            });
+        }
+
+        Ok(handle)
+    }
+}
+
+#[cfg(windows)]
+fn read_reparse_buffer(handle: *mut std::ffi::c_void, path: &PathBuf) -> Result<Vec<u8>, LinkError> {
+    unsafe {
+        let mut buffer = vec![0u8; MAXIMUM_REPARSE_DATA_BUFFER_SIZE];
+        let mut bytes_returned = 0u32;
+
+        let ok = DeviceIoControl(
+            handle,
+            FSCTL_GET_REPARSE_POINT,
+            ptr::null(),
+            0,
+            buffer.as_mut_ptr() as *mut _,
+            buffer.len() as u32,
+            &mut bytes_returned,
+            ptr::null(),
+        ) != 0;
+
+        if !ok {
+            return Err(LinkError {
+                message: format!("{:?} has no reparse point", path),
+                exit_code: 6,
+            });
+        }
+
+        Ok(buffer)
+    }
+}
+
+#[cfg(windows)]
+fn inspect_reparse_point(path: &PathBuf) -> Result<ReparseInfo, LinkError> {
+    unsafe {
+        let handle = open_reparse_point(path, GENERIC_READ)?;
+        let buffer = read_reparse_buffer(handle, path);
+        CloseHandle(handle);
+        let buffer = buffer?;
+
+        let tag = u32::from_le_bytes(buffer[0..4].try_into().unwrap());
+
+        // SubstituteNameOffset/Length and PrintNameOffset/Length sit right after the
+        // 8-byte common header; symlinks additionally carry a 4-byte Flags field before
+        // the path buffer, so the path buffer itself starts 4 bytes later than a junction's.
+        let (path_buffer_start, tag_name) = match tag {
+            IO_REPARSE_TAG_MOUNT_POINT => (16usize, "Junction"),
+            IO_REPARSE_TAG_SYMLINK => (20usize, "Symbolic Link"),
+            _ => {
+                return Err(LinkError {
+                    message: format!("{:?} has an unrecognized reparse tag (0x{:08X})", path, tag),
+                    exit_code: 6,
+                });
+            }
+        };
+
+        let substitute_offset = u16::from_le_bytes(buffer[8..10].try_into().unwrap()) as usize;
+        let substitute_len = u16::from_le_bytes(buffer[10..12].try_into().unwrap()) as usize;
+        let print_offset = u16::from_le_bytes(buffer[12..14].try_into().unwrap()) as usize;
+        let print_len = u16::from_le_bytes(buffer[14..16].try_into().unwrap()) as usize;
+
+        let name = if print_len > 0 {
+            decode_utf16_name(&buffer, path_buffer_start + print_offset, print_len)
+        } else {
+            decode_utf16_name(&buffer, path_buffer_start + substitute_offset, substitute_len)
+        };
+
+        let target = name.strip_prefix("\\??\\").unwrap_or(&name).to_string();
+
+        Ok(ReparseInfo { tag_name, target })
+    }
+}
+
+/// Unix has no junctions and a single symlink type, so inspection is just `readlink`.
+#[cfg(not(windows))]
+fn inspect_reparse_point(path: &PathBuf) -> Result<ReparseInfo, LinkError> {
+    let metadata = std::fs::symlink_metadata(path).map_err(|e| LinkError {
+        message: format!("Failed to stat {:?}: {}", path, e),
+        exit_code: 6,
+    })?;
+
+    if !metadata.file_type().is_symlink() {
+        return Err(LinkError {
+            message: format!("{:?} is not a symlink", path),
+            exit_code: 6,
+        });
+    }
+
+    let target = std::fs::read_link(path).map_err(|e| LinkError {
+        message: format!("Failed to read symlink {:?}: {}", path, e),
+        exit_code: 6,
+    })?;
+
+    Ok(ReparseInfo {
+        tag_name: "Symbolic Link",
+        target: target.display().to_string(),
+    })
+}
+
+#[cfg(windows)]
+fn decode_utf16_name(buffer: &[u8], byte_offset: usize, byte_len: usize) -> String {
+    let slice = &buffer[byte_offset..byte_offset + byte_len];
+    let wide: Vec<u16> = slice
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    String::from_utf16_lossy(&wide)
+}
+
+#[cfg(windows)]
+fn remove_reparse_point(path: &PathBuf) -> Result<(), LinkError> {
+    unsafe {
+        let handle = open_reparse_point(path, GENERIC_WRITE)?;
+
+        let result = (|| {
+            let buffer = read_reparse_buffer(handle, path)?;
+            let tag = u32::from_le_bytes(buffer[0..4].try_into().unwrap());
+
+            // FSCTL_DELETE_REPARSE_POINT only inspects ReparseTag and requires
+            // ReparseDataLength to be zero, so the GUID and data buffer stay zeroed.
+            let mut delete_buffer = [0u8; REPARSE_GUID_DATA_BUFFER_HEADER_SIZE];
+            delete_buffer[0..4].copy_from_slice(&tag.to_le_bytes());
+
+            let mut bytes_returned = 0u32;
+            let ok = DeviceIoControl(
+                handle,
+                FSCTL_DELETE_REPARSE_POINT,
+                delete_buffer.as_ptr() as *const _,
+                delete_buffer.len() as u32,
+                ptr::null_mut(),
+                0,
+                &mut bytes_returned,
+                ptr::null(),
+            ) != 0;
+
+            if ok {
+                Ok(())
+            } else {
+                Err(LinkError {
+                    message: format!("Failed to delete reparse point at {:?}", path),
+                    exit_code: 6,
+                })
+            }
+        })();
+
+        CloseHandle(handle);
+        result
+    }
+}
+
+fn remove_link(path: &PathBuf) -> Result<(), LinkError> {
+    let metadata = std::fs::symlink_metadata(path).map_err(|e| LinkError {
+        message: format!("Failed to stat {:?}: {}", path, e),
+        exit_code: 6,
+    })?;
+
+    #[cfg(windows)]
+    {
+        // `Metadata::is_dir` is false for junctions and directory symlinks on Windows,
+        // so test the raw directory attribute to route them to the reparse-delete path.
+        if metadata.file_attributes() & FILE_ATTRIBUTE_DIRECTORY != 0 {
+            remove_reparse_point(path)?;
+            return std::fs::remove_dir(path).map_err(|e| LinkError {
+                message: format!("Failed to remove junction directory {:?}: {}", path, e),
+                exit_code: 6,
+            });
+        }
+    }
+
+    #[cfg(not(windows))]
+    {
+        // A plain directory (as opposed to a symlink, which `symlink_metadata` never
+        // reports as a directory) isn't a link at all, so there's nothing safe to unlink.
+        if metadata.is_dir() {
+            return Err(LinkError {
+                message: format!("{:?} is a directory, not a link", path),
+                exit_code: 6,
+            });
+        }
+    }
+
+    std::fs::remove_file(path).map_err(|e| LinkError {
+        message: format!("Failed to remove {:?}: {}", path, e),
+        exit_code: 6,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ManifestEntry {
+    relative_path: PathBuf,
+    link_type: LinkType,
+    target: String,
+}
+
+fn export_manifest(tree: &PathBuf, manifest_path: &PathBuf) -> Result<(), LinkError> {
+    let mut entries = Vec::new();
+    let mut hard_link_groups: HashMap<(u64, u64), PathBuf> = HashMap::new();
+    walk_tree(tree, tree, &mut entries, &mut hard_link_groups)?;
+
+    let json = serde_json::to_string_pretty(&entries).map_err(|e| LinkError {
+        message: format!("Failed to serialize manifest: {}", e),
+        exit_code: 9,
+    })?;
+
+    std::fs::write(manifest_path, json).map_err(|e| LinkError {
+        message: format!("Failed to write manifest {:?}: {}", manifest_path, e),
+        exit_code: 9,
+    })
+}
+
+fn walk_tree(
+    root: &PathBuf,
+    dir: &PathBuf,
+    entries: &mut Vec<ManifestEntry>,
+    hard_link_groups: &mut HashMap<(u64, u64), PathBuf>,
+) -> Result<(), LinkError> {
+    let read_dir = std::fs::read_dir(dir).map_err(|e| LinkError {
+        message: format!("Failed to read directory {:?}: {}", dir, e),
+        exit_code: 9,
+    })?;
+
+    for entry in read_dir {
+        let entry = entry.map_err(|e| LinkError {
+            message: format!("Failed to read an entry of {:?}: {}", dir, e),
+            exit_code: 9,
+        })?;
+        let path = entry.path();
+
+        let metadata = std::fs::symlink_metadata(&path).map_err(|e| LinkError {
+            message: format!("Failed to stat {:?}: {}", path, e),
+            exit_code: 9,
+        })?;
+        let relative_path = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+
+        #[cfg(windows)]
+        {
+            let attributes = metadata.file_attributes();
+
+            if attributes & FILE_ATTRIBUTE_REPARSE_POINT != 0 {
+                let info = inspect_reparse_point(&path)?;
+                let link_type = if attributes & FILE_ATTRIBUTE_DIRECTORY != 0 {
+                    match info.tag_name {
+                        "Junction" => LinkType::Junction,
+                        _ => LinkType::Soft,
+                    }
+                } else {
+                    LinkType::Symbolic
+                };
+
+                entries.push(ManifestEntry {
+                    relative_path,
+                    link_type,
+                    target: info.target,
+                });
+                continue;
+            }
+        }
+
+        #[cfg(not(windows))]
+        {
+            if metadata.file_type().is_symlink() {
+                let target = std::fs::read_link(&path).map_err(|e| LinkError {
+                    message: format!("Failed to read symlink {:?}: {}", path, e),
+                    exit_code: 9,
+                })?;
+
+                entries.push(ManifestEntry {
+                    relative_path,
+                    link_type: LinkType::Symbolic,
+                    target: target.display().to_string(),
+                });
+                continue;
+            }
+        }
+
+        if metadata.is_dir() {
+            walk_tree(root, &path, entries, hard_link_groups)?;
+            continue;
+        }
+
+        if let Some(canonical) = hard_link_canonical_path(&path, hard_link_groups)? {
+            let relative_target = canonical.strip_prefix(root).unwrap_or(&canonical).to_path_buf();
+            entries.push(ManifestEntry {
+                relative_path,
+                link_type: LinkType::Hard,
+                target: relative_target.display().to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the first path seen for this file's (volume/device, file index/inode) pair if
+/// `path` is a hard link to it, or `None` if `path` is the first (or only) name for the file.
+#[cfg(windows)]
+fn hard_link_canonical_path(
+    path: &PathBuf,
+    groups: &mut HashMap<(u64, u64), PathBuf>,
+) -> Result<Option<PathBuf>, LinkError> {
+    let file = std::fs::File::open(path).map_err(|e| LinkError {
+        message: format!("Failed to open {:?}: {}", path, e),
+        exit_code: 9,
+    })?;
+
+    let mut info: ByHandleFileInformation = unsafe { std::mem::zeroed() };
+    let ok = unsafe { GetFileInformationByHandle(file.as_raw_handle() as *mut _, &mut info) };
+    if ok == 0 {
+        return Err(LinkError {
+            message: format!("Failed to query file information for {:?}", path),
+            exit_code: 9,
+        });
+    }
+
+    if info.number_of_links <= 1 {
+        return Ok(None);
+    }
+
+    let file_index = ((info.file_index_high as u64) << 32) | info.file_index_low as u64;
+    let key = (info.volume_serial_number as u64, file_index);
+
+    match groups.get(&key) {
+        Some(canonical) => Ok(Some(canonical.clone())),
+        None => {
+            groups.insert(key, path.clone());
+            Ok(None)
+        }
+    }
+}
+
+/// Unix has no volume serial number, so the device/inode pair from `stat` plays the same
+/// role: two paths sharing a (dev, ino) are the same hard-linked file.
+#[cfg(not(windows))]
+fn hard_link_canonical_path(
+    path: &PathBuf,
+    groups: &mut HashMap<(u64, u64), PathBuf>,
+) -> Result<Option<PathBuf>, LinkError> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = std::fs::metadata(path).map_err(|e| LinkError {
+        message: format!("Failed to stat {:?}: {}", path, e),
+        exit_code: 9,
+    })?;
+
+    if metadata.nlink() <= 1 {
+        return Ok(None);
+    }
+
+    let key = (metadata.dev(), metadata.ino());
+
+    match groups.get(&key) {
+        Some(canonical) => Ok(Some(canonical.clone())),
+        None => {
+            groups.insert(key, path.clone());
+            Ok(None)
+        }
+    }
+}
+
+fn import_manifest(manifest_path: &PathBuf, root: &PathBuf) -> Result<(), LinkError> {
+    let data = std::fs::read_to_string(manifest_path).map_err(|e| LinkError {
+        message: format!("Failed to read manifest {:?}: {}", manifest_path, e),
+        exit_code: 9,
+    })?;
+
+    let entries: Vec<ManifestEntry> = serde_json::from_str(&data).map_err(|e| LinkError {
+        message: format!("Failed to parse manifest {:?}: {}", manifest_path, e),
+        exit_code: 9,
+    })?;
+
+    for entry in entries {
+        let link_path = root.join(&entry.relative_path);
+        if let Some(parent) = link_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| LinkError {
+                message: format!("Failed to create directory {:?}: {}", parent, e),
+                exit_code: 9,
+            })?;
+        }
+
+        // Hard-link targets are recorded relative to the export root (so they still
+        // resolve after being recreated under a different root); junction/symbolic/soft
+        // targets are replayed exactly as recorded, absolute or relative, without rewriting.
+        let target = match entry.link_type {
+            LinkType::Hard => root.join(&entry.target),
+            _ => PathBuf::from(&entry.target),
+        };
+        create_link(entry.link_type, &link_path, &target)?;
+    }
+
+    Ok(())
+}
+
+fn print_inspect_result(path: &PathBuf, info: &ReparseInfo) {
+    println!(
+        "{} {:?} {} {} {}",
+        info.tag_name.bright_green(),
+        path.bright_green(),
+        "→".bright_green(),
+        "points to".bright_green(),
+        info.target.bright_green()
+    );
+}
+
 fn print_success(link_type: LinkType, src: &PathBuf, dst: &PathBuf) {
     println!(
         "{} {} {} {:?}, {} {:?}",